@@ -1,3 +1,8 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use num_bigint::{BigInt, BigUint, RandBigInt};
+use num_integer::Integer;
+use num_traits::{One, Signed, Zero};
+use rand::Rng;
 use toy_rsa_lib::rsa_prime;
 
 /// Fixed RSA encryption exponent.
@@ -41,6 +46,326 @@ pub fn genkey() -> (u32, u32) {
     }
 }
 
+/// Generate a pair of `bits`-bit prime numbers suitable for RSA encryption.
+///
+/// Unlike [`genkey`], which is capped at the 32-bit primes produced by
+/// `rsa_prime`, this generates arbitrary-width primes using `num-bigint`, so a
+/// caller can request the factors for, say, a 2048-bit modulus by asking for
+/// 1024-bit primes. Candidates are drawn at random, forced odd, and rejected
+/// unless they are prime and leave `EXP` coprime with the totient.
+///
+/// # Returns
+///
+/// A tuple `(p, q)` of `bits`-bit primes whose totient is coprime with `EXP`.
+pub fn genkey_bits(bits: usize) -> (BigUint, BigUint) {
+    let exp = BigUint::from(EXP);
+    loop {
+        let p = random_prime(bits);
+        let q = random_prime(bits);
+        if p == q {
+            continue;
+        }
+
+        let totient = (&p - 1u32) * (&q - 1u32);
+        if mod_inverse_big(&exp, &totient).is_some() {
+            return (p, q);
+        }
+    }
+}
+
+/// Number of Miller–Rabin rounds used by the key generator, giving a failure
+/// probability of at most `4^-MR_ROUNDS`.
+const MR_ROUNDS: usize = 40;
+
+/// Small primes used to cheaply reject most composite candidates before the
+/// comparatively expensive Miller–Rabin rounds.
+const SMALL_PRIMES: [u32; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+/// Generate a random `bits`-bit probable prime.
+///
+/// A single full-width odd candidate is drawn and then stepped by 2 (skipping
+/// evens) until [`is_prime`] accepts it, which scans far faster than redrawing
+/// a fresh random candidate each time.
+fn random_prime(bits: usize) -> BigUint {
+    let mut rng = rand::thread_rng();
+    // Force the top bit (full width) and the bottom bit (odd).
+    let mut n = rng.gen_biguint(bits as u64);
+    n.set_bit(bits as u64 - 1, true);
+    n.set_bit(0, true);
+    let two = BigUint::from(2u32);
+    loop {
+        if is_prime(&n, MR_ROUNDS) {
+            return n;
+        }
+        n += &two;
+    }
+}
+
+/// Test whether `n` is prime using the Miller–Rabin probabilistic test.
+///
+/// The candidate is first trial-divided by [`SMALL_PRIMES`]; survivors are then
+/// subjected to `rounds` Miller–Rabin witnesses. Writing `n - 1 = 2^s · d` with
+/// `d` odd, each round picks a random base `a` in `[2, n - 2]`, computes
+/// `x = a^d mod n`, and — unless `x` is `1` or `n - 1` — squares `x` up to
+/// `s - 1` times looking for `n - 1`; if none is found, `n` is composite.
+///
+/// A prime is always reported prime; a composite survives all `rounds` with
+/// probability at most `4^-rounds`.
+pub fn is_prime(n: &BigUint, rounds: usize) -> bool {
+    let one = BigUint::one();
+    let two = &one + &one;
+    if n < &two {
+        return false;
+    }
+    for p in SMALL_PRIMES {
+        let p = BigUint::from(p);
+        if n == &p {
+            return true;
+        }
+        if (n % &p).is_zero() {
+            return false;
+        }
+    }
+
+    // Write n - 1 = 2^s * d with d odd.
+    let n_minus_one = n - &one;
+    let mut d = n_minus_one.clone();
+    let mut s = 0u64;
+    while !d.bit(0) {
+        d >>= 1;
+        s += 1;
+    }
+
+    let mut rng = rand::thread_rng();
+    let upper = n - &two; // inclusive upper bound for the base
+    'witness: for _ in 0..rounds {
+        let a = rng.gen_biguint_range(&two, &(upper.clone() + &one));
+        let mut x = a.modpow(&d, n);
+        if x == one || x == n_minus_one {
+            continue;
+        }
+        for _ in 0..s.saturating_sub(1) {
+            x = x.modpow(&two, n);
+            if x == n_minus_one {
+                continue 'witness;
+            }
+        }
+        return false;
+    }
+    true
+}
+
+/// Encrypt the plaintext `msg` using the arbitrary-width RSA modulus `n`.
+///
+/// This is the `BigUint` analogue of [`encrypt`]: it raises `msg` to the public
+/// exponent `EXP` modulo `n` via [`BigUint::modpow`], avoiding the fixed-width
+/// `modexp` whose `u128` intermediate products overflow for real key sizes.
+pub fn encrypt_big(n: &BigUint, msg: &BigUint) -> BigUint {
+    msg.modpow(&BigUint::from(EXP), n)
+}
+
+/// Decrypt the ciphertext `msg` using the arbitrary-width private factors `key`.
+///
+/// The `BigUint` analogue of [`decrypt`]: it recovers the private exponent `d`
+/// from the factor pair and raises `msg` to `d` modulo `n = p * q`.
+pub fn decrypt_big(key: &(BigUint, BigUint), msg: &BigUint) -> BigUint {
+    let (p, q) = key;
+    let totient = (p - 1u32) * (q - 1u32);
+    let d = mod_inverse_big(&BigUint::from(EXP), &totient).unwrap();
+    decrypt_crt(p, q, &d, msg)
+}
+
+/// Decrypt `c` with the Chinese Remainder Theorem using the private factors.
+///
+/// Rather than one full-width `c^d mod n`, this exponentiates modulo each factor
+/// — roughly 3–4× faster, matching how production RSA libraries decrypt. With
+/// `dp = d mod (p-1)`, `dq = d mod (q-1)`, and `qinv = q^{-1} mod p`, it computes
+/// `m1 = c^dp mod p`, `m2 = c^dq mod q`, `h = qinv·(m1 - m2) mod p`, and returns
+/// `m = m2 + h·q`.
+fn decrypt_crt(p: &BigUint, q: &BigUint, d: &BigUint, c: &BigUint) -> BigUint {
+    let dp = d % (p - 1u32);
+    let dq = d % (q - 1u32);
+    let qinv = mod_inverse_big(q, p).unwrap();
+
+    let m1 = c.modpow(&dp, p);
+    let m2 = c.modpow(&dq, q);
+
+    // h = qinv * (m1 - m2) mod p, keeping the subtraction non-negative.
+    let p_int = BigInt::from(p.clone());
+    let diff = (BigInt::from(m1) - BigInt::from(m2.clone())).mod_floor(&p_int);
+    let h = (BigInt::from(qinv) * diff).mod_floor(&p_int);
+
+    m2 + h.to_biguint().unwrap() * q
+}
+
+/// Encrypt `msg` under the modulus `n` with PKCS#1 v1.5 padding.
+///
+/// The message is wrapped in an encryption block `0x00 || 0x02 || PS || 0x00 ||
+/// M`, where `PS` is at least 8 random nonzero bytes chosen so the block fills
+/// the modulus width. The randomised padding makes encryption non-deterministic
+/// so repeated or short plaintexts no longer produce identical ciphertexts.
+///
+/// The padding needs at least 11 bytes of modulus headroom, so `n` must be a
+/// wide modulus from [`genkey_bits`]; the 8-byte `u64` modulus from [`genkey`]
+/// is too small. This is why the default [`encrypt`] stays textbook rather than
+/// routing through here.
+///
+/// # Panics
+///
+/// Panics if `msg` is too long to pad, i.e. longer than `k - 11` bytes where
+/// `k` is the modulus width in bytes.
+pub fn encrypt_pkcs1(n: &BigUint, msg: &[u8]) -> BigUint {
+    let k = modulus_bytes(n);
+    assert!(msg.len() + 11 <= k, "message too long for modulus");
+
+    let mut rng = rand::thread_rng();
+    let ps_len = k - 3 - msg.len();
+    let mut block = Vec::with_capacity(k);
+    block.push(0x00);
+    block.push(0x02);
+    for _ in 0..ps_len {
+        // Padding bytes must be nonzero so the 0x00 separator is unambiguous.
+        block.push(rng.gen_range(1..=255));
+    }
+    block.push(0x00);
+    block.extend_from_slice(msg);
+
+    encrypt_big(n, &BigUint::from_bytes_be(&block))
+}
+
+/// Decrypt a PKCS#1 v1.5 padded ciphertext produced by [`encrypt_pkcs1`].
+///
+/// Exponentiates with the private factors, then strips the `0x00 || 0x02 || PS
+/// || 0x00` header and returns the recovered message. Returns `Err` if the
+/// block does not carry a well-formed padding marker.
+pub fn decrypt_pkcs1(key: &(BigUint, BigUint), msg: &BigUint) -> Result<Vec<u8>, &'static str> {
+    let (p, q) = key;
+    let k = modulus_bytes(&(p * q));
+    let m = decrypt_big(key, msg);
+
+    // Left-pad to the full modulus width so the leading 0x00 is present.
+    let mut block = m.to_bytes_be();
+    if block.len() > k {
+        return Err("decrypted block exceeds modulus width");
+    }
+    if block.len() < k {
+        let mut padded = vec![0u8; k - block.len()];
+        padded.extend_from_slice(&block);
+        block = padded;
+    }
+
+    if block[0] != 0x00 || block[1] != 0x02 {
+        return Err("malformed PKCS#1 padding marker");
+    }
+    // Skip the random padding, then require the 0x00 separator.
+    let sep = block[2..]
+        .iter()
+        .position(|&b| b == 0x00)
+        .map(|i| i + 2)
+        .ok_or("missing PKCS#1 separator")?;
+    if sep < 10 {
+        // Fewer than 8 padding bytes: reject.
+        return Err("PKCS#1 padding too short");
+    }
+    Ok(block[sep + 1..].to_vec())
+}
+
+/// Width of the modulus `n` in bytes.
+fn modulus_bytes(n: &BigUint) -> usize {
+    n.bits().div_ceil(8) as usize
+}
+
+/// An RSA public key: modulus `n` and public exponent `e`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PublicKey {
+    pub n: u64,
+    pub e: u64,
+}
+
+/// An RSA private key: modulus `n`, private exponent `d`, and the factors `(p, q)`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PrivateKey {
+    pub n: u64,
+    pub d: u64,
+    pub p: u32,
+    pub q: u32,
+}
+
+/// Generate a fresh key pair as structured [`PublicKey`]/[`PrivateKey`] values.
+///
+/// A convenience over [`genkey`] that packages the prime pair into the
+/// serializable key types, computing the private exponent `d` once up front.
+pub fn genkey_pair() -> (PublicKey, PrivateKey) {
+    let (p, q) = genkey();
+    let n = p as u64 * q as u64;
+    let totient = (p - 1) as u64 * (q - 1) as u64;
+    let d = mod_inverse(EXP, totient).unwrap();
+    (PublicKey { n, e: EXP }, PrivateKey { n, d, p, q })
+}
+
+impl PublicKey {
+    /// Encrypt the plaintext `msg` under this public key.
+    pub fn encrypt(&self, msg: u32) -> u64 {
+        modexp(msg as u64, self.e, self.n)
+    }
+
+    /// Serialize the key to a base64 string encoding `n` and `e`.
+    pub fn to_base64(&self) -> String {
+        let mut bytes = Vec::with_capacity(16);
+        bytes.extend_from_slice(&self.n.to_be_bytes());
+        bytes.extend_from_slice(&self.e.to_be_bytes());
+        STANDARD.encode(bytes)
+    }
+
+    /// Reload a key previously produced by [`PublicKey::to_base64`].
+    pub fn from_base64(s: &str) -> Result<PublicKey, &'static str> {
+        let bytes = STANDARD.decode(s).map_err(|_| "invalid base64")?;
+        if bytes.len() != 16 {
+            return Err("malformed public key");
+        }
+        Ok(PublicKey {
+            n: u64::from_be_bytes(bytes[0..8].try_into().unwrap()),
+            e: u64::from_be_bytes(bytes[8..16].try_into().unwrap()),
+        })
+    }
+}
+
+impl PrivateKey {
+    /// The [`PublicKey`] corresponding to this private key.
+    pub fn public(&self) -> PublicKey {
+        PublicKey { n: self.n, e: EXP }
+    }
+
+    /// Decrypt the ciphertext `msg` under this private key.
+    pub fn decrypt(&self, msg: u64) -> u32 {
+        decrypt((self.p, self.q), msg)
+    }
+
+    /// Serialize the key to a base64 string encoding `n`, `d`, `p`, and `q`.
+    pub fn to_base64(&self) -> String {
+        let mut bytes = Vec::with_capacity(24);
+        bytes.extend_from_slice(&self.n.to_be_bytes());
+        bytes.extend_from_slice(&self.d.to_be_bytes());
+        bytes.extend_from_slice(&self.p.to_be_bytes());
+        bytes.extend_from_slice(&self.q.to_be_bytes());
+        STANDARD.encode(bytes)
+    }
+
+    /// Reload a key previously produced by [`PrivateKey::to_base64`].
+    pub fn from_base64(s: &str) -> Result<PrivateKey, &'static str> {
+        let bytes = STANDARD.decode(s).map_err(|_| "invalid base64")?;
+        if bytes.len() != 24 {
+            return Err("malformed private key");
+        }
+        Ok(PrivateKey {
+            n: u64::from_be_bytes(bytes[0..8].try_into().unwrap()),
+            d: u64::from_be_bytes(bytes[8..16].try_into().unwrap()),
+            p: u32::from_be_bytes(bytes[16..20].try_into().unwrap()),
+            q: u32::from_be_bytes(bytes[20..24].try_into().unwrap()),
+        })
+    }
+}
+
 fn gcd(a: u64, b: u64) -> u64 {
     let mut a = a;
     let mut b = b;
@@ -56,6 +381,17 @@ fn gcd(a: u64, b: u64) -> u64 {
 ///
 /// This function takes a plaintext message `msg` as a 32-bit unsigned integer and encrypts it using the RSA public key `key`. The result is the ciphertext.
 ///
+/// This bare-modulus form is retained as a compatibility shim for the
+/// bare-integer interface; [`PublicKey::encrypt`] is the type-accepting
+/// equivalent that takes a structured [`PublicKey`].
+///
+/// This is textbook RSA: the encryption is deterministic and unpadded, so equal
+/// plaintexts produce equal ciphertexts. It stays that way because the 8-byte
+/// (`u64`) modulus from [`genkey`]/[`genkey_pair`] is too small to hold a PKCS#1
+/// block. For non-deterministic, semantically secure encryption use
+/// [`encrypt_pkcs1`]/[`decrypt_pkcs1`] with a wider modulus generated by
+/// [`genkey_bits`].
+///
 /// # Arguments
 ///
 /// * `key` - The RSA public key used for encryption.
@@ -75,13 +411,17 @@ fn gcd(a: u64, b: u64) -> u64 {
 /// println!("Ciphertext: {}", ciphertext);
 /// ```
 pub fn encrypt(key: u64, msg: u32) -> u64 {
-    modexp(msg.try_into().unwrap(), EXP, key)
+    modexp(msg.into(), EXP, key)
 }
 
 /// Decrypt the ciphertext `msg` using the RSA private `key` and return the resulting plaintext.
 ///
 /// This function takes a ciphertext message `msg` and decrypts it using the RSA private key `key`. The result is the original plaintext message.
 ///
+/// This raw-tuple form is retained as a compatibility shim for the bare-integer
+/// interface; [`PrivateKey::decrypt`] is the type-accepting equivalent that takes
+/// a structured [`PrivateKey`].
+///
 /// # Arguments
 ///
 /// * `key` - The RSA private key used for decryption, represented as a tuple of two 32-bit unsigned integers (p and q).
@@ -101,11 +441,140 @@ pub fn encrypt(key: u64, msg: u32) -> u64 {
 /// println!("Plaintext: {}", plaintext);
 /// ```
 pub fn decrypt(key: (u32, u32), msg: u64) -> u32 {
+    let (p, q) = (key.0 as u64, key.1 as u64);
+    let totient = (p - 1) * (q - 1);
+    let d = mod_inverse(EXP, totient).unwrap();
+
+    // Chinese Remainder Theorem recombination: exponentiate modulo each factor
+    // rather than once modulo `n`, which is ~3-4x cheaper and matches how
+    // production RSA libraries decrypt now that the factors are available.
+    let dp = d % (p - 1);
+    let dq = d % (q - 1);
+    let qinv = mod_inverse(q, p).unwrap();
+
+    let m1 = modexp(msg, dp, p);
+    let m2 = modexp(msg, dq, q);
+
+    // h = qinv * (m1 - m2) mod p, keeping the subtraction non-negative.
+    let diff = (m1 + p - m2 % p) % p;
+    let h = ((qinv as u128 * diff as u128) % p as u128) as u64;
+
+    (m2 + h * q).try_into().unwrap()
+}
+
+/// Plaintext block width in bytes. Three bytes keep each block below `2^24`,
+/// comfortably under the 62-bit-plus modulus and the `u32` message limit.
+const BLOCK_IN: usize = 3;
+
+/// Ciphertext block width in bytes — one big-endian `u64` per encrypted block.
+const BLOCK_OUT: usize = 8;
+
+/// Encrypt an arbitrary byte string under the RSA public `key`.
+///
+/// The stream is prefixed with the original length as a 4-byte big-endian
+/// header, then split into [`BLOCK_IN`]-byte plaintext blocks (zero-padding the
+/// final short block). Each block is encrypted with the [`encrypt`] primitive
+/// and emitted as a fixed-width big-endian [`BLOCK_OUT`]-byte ciphertext block.
+/// The explicit length lets [`decrypt_bytes`] recover messages whose real final
+/// byte is `0x00` rather than guessing padding from trailing zeros. Reverse with
+/// [`decrypt_bytes`].
+///
+/// # Arguments
+///
+/// * `key` - The RSA private key as the prime pair `(p, q)`.
+/// * `data` - The message bytes to encrypt.
+pub fn encrypt_bytes(key: (u32, u32), data: &[u8]) -> Vec<u8> {
+    let n = key.0 as u64 * key.1 as u64;
+    let mut stream = Vec::with_capacity(4 + data.len());
+    stream.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    stream.extend_from_slice(data);
+
+    let mut out = Vec::with_capacity(stream.len().div_ceil(BLOCK_IN) * BLOCK_OUT);
+    for chunk in stream.chunks(BLOCK_IN) {
+        let mut block = [0u8; 4];
+        block[4 - BLOCK_IN..][..chunk.len()].copy_from_slice(chunk);
+        let c = encrypt(n, u32::from_be_bytes(block));
+        out.extend_from_slice(&c.to_be_bytes());
+    }
+    out
+}
+
+/// Decrypt a byte string produced by [`encrypt_bytes`] under the private `key`.
+///
+/// Consumes fixed-width [`BLOCK_OUT`]-byte ciphertext blocks, decrypts each back
+/// to its [`BLOCK_IN`]-byte plaintext block, reads the 4-byte big-endian length
+/// header, and returns exactly that many message bytes — so any plaintext,
+/// including one ending in `0x00`, round-trips exactly.
+pub fn decrypt_bytes(key: (u32, u32), data: &[u8]) -> Vec<u8> {
+    let mut stream = Vec::with_capacity(data.len() / BLOCK_OUT * BLOCK_IN);
+    for chunk in data.chunks(BLOCK_OUT) {
+        let mut block = [0u8; 8];
+        block[..chunk.len()].copy_from_slice(chunk);
+        let m = decrypt(key, u64::from_be_bytes(block));
+        stream.extend_from_slice(&m.to_be_bytes()[4 - BLOCK_IN..]);
+    }
+
+    if stream.len() < 4 {
+        return Vec::new();
+    }
+    let len = u32::from_be_bytes(stream[0..4].try_into().unwrap()) as usize;
+    // Guard against a truncated or corrupt header that overruns the buffer.
+    if 4 + len > stream.len() {
+        return Vec::new();
+    }
+    stream[4..4 + len].to_vec()
+}
+
+/// Sign `msg` with the RSA private `key` and return the signature.
+///
+/// The message is reduced to a fixed-width digest (so signatures cover
+/// arbitrary-length input) and the private exponent `d` is applied to it with
+/// [`modexp`], mirroring the encrypt/decrypt primitives. The returned value is
+/// the signature, verifiable with [`verify`].
+///
+/// # Security
+///
+/// This is a toy: the digest is a 32-bit non-cryptographic FNV-1a hash, so
+/// collisions are trivial to construct and distinct messages routinely share a
+/// signature. It demonstrates the RSA signing primitive only and must not be
+/// used where signatures need to be unforgeable.
+///
+/// # Arguments
+///
+/// * `key` - The RSA private key as the prime pair `(p, q)`.
+/// * `msg` - The message bytes to sign.
+pub fn sign(key: (u32, u32), msg: &[u8]) -> u64 {
+    let n = key.0 as u64 * key.1 as u64;
     let totient = (key.0 - 1) as u64 * (key.1 - 1) as u64;
     let d = mod_inverse(EXP, totient).unwrap();
-    modexp(msg, d, key.0 as u64 * key.1 as u64)
-        .try_into()
-        .unwrap()
+    modexp(digest(msg), d, n)
+}
+
+/// Verify that `sig` is a valid signature over `msg` for the public modulus.
+///
+/// Applies the public exponent `EXP` to `sig` with [`modexp`] and compares the
+/// result against the recomputed digest of `msg`, returning `true` on a match.
+///
+/// # Arguments
+///
+/// * `pubkey` - The RSA public modulus `n = p * q`.
+/// * `msg` - The message bytes that were supposedly signed.
+/// * `sig` - The signature produced by [`sign`].
+pub fn verify(pubkey: u64, msg: &[u8], sig: u64) -> bool {
+    modexp(sig, EXP, pubkey) == digest(msg)
+}
+
+/// Reduce an arbitrary-length message to a fixed-width digest via FNV-1a.
+///
+/// The 32-bit result always fits below the 62-bit-plus modulus, so it can be
+/// fed straight into [`modexp`] as the signed representative.
+fn digest(msg: &[u8]) -> u64 {
+    let mut hash: u32 = 0x811c_9dc5;
+    for &byte in msg {
+        hash ^= byte as u32;
+        hash = hash.wrapping_mul(0x0100_0193);
+    }
+    hash as u64
 }
 
 /// Performs modular exponentiation.
@@ -210,6 +679,41 @@ fn mod_inverse(a: u64, m: u64) -> Option<u64> {
     Some(t as u64)
 }
 
+/// Calculate the modular multiplicative inverse of `a` modulo `m` over `BigUint`.
+///
+/// The arbitrary-width analogue of [`mod_inverse`]: returns `Some(x)` with
+/// `(a * x) % m == 1`, or `None` when `a` and `m` are not coprime.
+fn mod_inverse_big(a: &BigUint, m: &BigUint) -> Option<BigUint> {
+    let m_int = BigInt::from(m.clone());
+
+    let mut t = BigInt::zero();
+    let mut newt = BigInt::one();
+    let mut r = m_int.clone();
+    let mut newr = BigInt::from(a.clone());
+
+    while !newr.is_zero() {
+        let quotient = &r / &newr;
+
+        let tmp = t - &quotient * &newt;
+        t = newt;
+        newt = tmp;
+
+        let tmp = r - &quotient * &newr;
+        r = newr;
+        newr = tmp;
+    }
+
+    if r > BigInt::one() {
+        return None;
+    }
+
+    if t.is_negative() {
+        t += &m_int;
+    }
+
+    t.to_biguint()
+}
+
 // Print a usage error message and exit.
 fn error(e: &str) -> ! {
     eprintln!("Error: {}", e);
@@ -245,7 +749,7 @@ mod tests {
     fn test_modexp() {
         // Largest prime less than 2**64.
         // https://primes.utm.edu/lists/2small/0bit.html
-        let bigm = u64::max_value() - 58;
+        let bigm = u64::MAX - 58;
         assert_eq!(0, modexp(bigm - 2, bigm - 1, 1));
         assert_eq!(1, modexp(bigm - 2, bigm - 1, bigm));
         assert_eq!(827419628471527655, modexp(bigm - 2, (1 << 32) + 1, bigm));
@@ -255,6 +759,60 @@ mod tests {
         assert_eq!(34, modexp(450, 768, 517));
     }
 
+    // Known-answer test for the Miller–Rabin primality routine.
+    #[test]
+    fn test_is_prime() {
+        // Accepts a prime.
+        assert!(is_prime(&BigUint::from(7919u32), 40));
+        // Rejects an odd composite.
+        assert!(!is_prime(&BigUint::from(7917u32), 40));
+        // Rejects the Carmichael number 561, which fools the Fermat test.
+        assert!(!is_prime(&BigUint::from(561u32), 40));
+    }
+
+    // Round-trip a signature and reject a tampered message.
+    #[test]
+    fn test_sign_verify_roundtrip() {
+        let key = genkey();
+        let n = key.0 as u64 * key.1 as u64;
+        let msg = b"attack at dawn";
+
+        let sig = sign(key, msg);
+        assert!(verify(n, msg, sig));
+        assert!(!verify(n, b"attack at dusk", sig));
+    }
+
+    // Round-trip arbitrary bytes, including a trailing NUL, through block mode.
+    #[test]
+    fn test_encrypt_decrypt_bytes_roundtrip() {
+        let key = genkey();
+        for msg in [
+            &b""[..],
+            &b"hello, world"[..],
+            &b"exactly-seven!"[..],
+            &[0x00, 0x10, 0x00][..],
+            &b"ends in nul\0"[..],
+        ] {
+            let ciphertext = encrypt_bytes(key, msg);
+            assert_eq!(decrypt_bytes(key, &ciphertext), msg);
+        }
+    }
+
+    // Round-trip a key pair through base64 export/import and encrypt with it.
+    #[test]
+    fn test_key_serialization_roundtrip() {
+        let (pubkey, privkey) = genkey_pair();
+
+        let pubkey2 = PublicKey::from_base64(&pubkey.to_base64()).unwrap();
+        let privkey2 = PrivateKey::from_base64(&privkey.to_base64()).unwrap();
+        assert_eq!(pubkey, pubkey2);
+        assert_eq!(privkey, privkey2);
+
+        let mut rng = rand::thread_rng();
+        let msg: u32 = rng.gen();
+        assert_eq!(privkey2.decrypt(pubkey2.encrypt(msg)), msg);
+    }
+
     // encrypt a random u32 10 times and check the result - tests decrypt, encrypt and genkey
     #[test]
     fn test_random_10_rsa_encryption_decryption() {
@@ -269,10 +827,7 @@ mod tests {
             println!("message: {}", original_message);
 
             // Encrypt the random number
-            let encrypted_message = encrypt(
-                (key.0 as u64 * key.1 as u64).try_into().unwrap(),
-                original_message as u32,
-            );
+            let encrypted_message = encrypt(key.0 as u64 * key.1 as u64, original_message);
 
             // Decrypt the encrypted message
             let decrypted_message = decrypt(key, encrypted_message);